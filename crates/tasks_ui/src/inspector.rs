@@ -0,0 +1,378 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use gpui::{
+    actions, AppContext, EventEmitter, FocusHandle, FocusableView, IntoElement, Model,
+    ParentElement, Pixels, Render, SharedString, Styled, View, ViewContext, WeakView,
+    WindowContext,
+};
+use ui::{h_flex, px, v_flex, ButtonCommon, Clickable, IconButton, IconName, Label, LabelCommon};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    item::ItemHandle,
+    persistence::model::SerializedTaskDefinition,
+    Pane, Workspace,
+};
+
+use crate::{modal::Spawn, status_indicator::TaskStatus};
+
+/// How many finished task runs we keep around after their terminal tab closes, so the
+/// aggregate red/yellow/green rollup in [`crate::status_indicator::TaskStatusIndicator`]
+/// can be drilled into instead of only being clickable to open the spawn modal.
+const MAX_HISTORY: usize = 64;
+
+/// How often [`TaskInspector`] polls `local_terminal_handles()` for runs that have dropped
+/// out of the live list (tab closed, or a status other than running/completed), so they
+/// still get recorded into `history` instead of vanishing.
+const COMPLETION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+actions!(task_inspector, [ToggleFocus]);
+
+/// Registers the inspector's `ToggleFocus` action on every new workspace. It does not add
+/// the [`TaskInspector`] panel to the workspace. Call once from the `tasks_ui` crate's
+/// top-level `init`.
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _cx| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<TaskInspector>(cx);
+        });
+    })
+    .detach();
+}
+
+/// A snapshot of one task run, live or finished, shown as a row in the [`TaskInspector`].
+#[derive(Clone)]
+pub struct TaskRunRecord {
+    pub label: SharedString,
+    pub status: TaskStatus,
+    pub started_at: Instant,
+    pub finished_at: Option<Instant>,
+    pub exit_code: Option<i32>,
+}
+
+impl TaskRunRecord {
+    fn duration(&self) -> Duration {
+        self.finished_at.unwrap_or_else(Instant::now) - self.started_at
+    }
+}
+
+pub enum TaskInspectorEvent {
+    Focus,
+}
+
+/// Extends the single status-bar icon into a full listing of every spawned task, live or
+/// recently finished: label, status, start time, duration, and exit code, with per-row
+/// actions to focus the task's terminal tab, re-run it, or kill it.
+pub struct TaskInspector {
+    workspace: WeakView<Workspace>,
+    history: VecDeque<TaskRunRecord>,
+    /// The live runs seen on the last completion poll, keyed by label, so a run that drops
+    /// out of `local_terminal_handles()` between polls can be recorded into `history` with
+    /// the status it had on that last poll, instead of just disappearing. A run that
+    /// completes while still in the live list is not recorded until it drops out.
+    last_live: HashMap<SharedString, TaskRunRecord>,
+    focus_handle: FocusHandle,
+}
+
+impl TaskInspector {
+    pub fn new(workspace: WeakView<Workspace>, cx: &mut ViewContext<Self>) -> Self {
+        let mut this = Self {
+            workspace,
+            history: VecDeque::new(),
+            last_live: HashMap::default(),
+            focus_handle: cx.focus_handle(),
+        };
+        this.watch_for_completions(cx);
+        this
+    }
+
+    /// Called by the completion poll whenever a run drops out of the live list, so the run
+    /// stays visible in the inspector's history after its tab closes.
+    pub fn record_finished(&mut self, record: TaskRunRecord, cx: &mut ViewContext<Self>) {
+        if self.history.len() >= MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(record);
+        cx.notify();
+    }
+
+    /// Polls `local_terminal_handles()` once per [`COMPLETION_POLL_INTERVAL`] and records a
+    /// run the moment it drops out of the live set, which is the only reliable signal this
+    /// crate has for "a task finished" short of a dedicated terminal event to subscribe to.
+    fn watch_for_completions(&mut self, cx: &mut ViewContext<Self>) {
+        cx.spawn(|this, mut cx| async move {
+            loop {
+                cx.background_executor().timer(COMPLETION_POLL_INTERVAL).await;
+                let Some(handle) = this.upgrade() else {
+                    break;
+                };
+                handle.update(&mut cx, |this, cx| {
+                    let live = this.live_runs(cx);
+                    let live_labels: HashSet<_> =
+                        live.iter().map(|run| run.label.clone()).collect();
+
+                    let newly_finished: Vec<_> = this
+                        .last_live
+                        .keys()
+                        .filter(|label| !live_labels.contains(*label))
+                        .cloned()
+                        .collect();
+                    for label in newly_finished {
+                        if let Some(mut record) = this.last_live.remove(&label) {
+                            record.finished_at = Some(Instant::now());
+                            let failed = matches!(record.status, TaskStatus::Failed);
+                            let workspace = this.workspace.clone();
+                            this.record_finished(record, cx);
+                            if failed {
+                                crate::supervisor::handle_failed_run(workspace, label, cx);
+                            }
+                        }
+                    }
+
+                    this.last_live = live
+                        .into_iter()
+                        .map(|run| (run.label.clone(), run))
+                        .collect();
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn live_runs(&self, cx: &ViewContext<Self>) -> Vec<TaskRunRecord> {
+        let Some(project) = self
+            .workspace
+            .upgrade()
+            .map(|workspace| workspace.read(cx).project().clone())
+        else {
+            return Vec::new();
+        };
+        let project = project.read(cx);
+
+        project
+            .local_terminal_handles()
+            .iter()
+            .filter_map(|handle| handle.upgrade())
+            .filter_map(|handle| {
+                let handle = handle.read(cx);
+                let task_state = handle.task()?;
+                let status = match task_state.status {
+                    terminal::TaskStatus::Running => TaskStatus::Running,
+                    terminal::TaskStatus::Completed { success: true } => TaskStatus::Succeeded,
+                    terminal::TaskStatus::Completed { success: false } => TaskStatus::Failed,
+                    _ => return None,
+                };
+                Some(TaskRunRecord {
+                    label: task_state.label.clone(),
+                    status,
+                    started_at: task_state.started_at,
+                    finished_at: None,
+                    exit_code: task_state.completion_status.and_then(|s| s.exit_code()),
+                })
+            })
+            .collect()
+    }
+}
+
+impl EventEmitter<TaskInspectorEvent> for TaskInspector {}
+impl EventEmitter<PanelEvent> for TaskInspector {}
+
+impl FocusableView for TaskInspector {
+    fn focus_handle(&self, _cx: &gpui::AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for TaskInspector {
+    fn persistent_name() -> &'static str {
+        "TaskInspector"
+    }
+
+    fn position(&self, _cx: &WindowContext) -> DockPosition {
+        DockPosition::Bottom
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Bottom | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, _position: DockPosition, _cx: &mut ViewContext<Self>) {}
+
+    fn size(&self, _cx: &WindowContext) -> Pixels {
+        px(240.)
+    }
+
+    fn set_size(&mut self, _size: Option<Pixels>, _cx: &mut ViewContext<Self>) {}
+
+    fn icon(&self, _cx: &WindowContext) -> Option<IconName> {
+        Some(IconName::ListTodo)
+    }
+
+    fn icon_tooltip(&self, _cx: &WindowContext) -> Option<&'static str> {
+        Some("Task Inspector")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+}
+
+impl Render for TaskInspector {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let live_runs = self.live_runs(cx);
+        let live_labels: Vec<_> = live_runs.iter().map(|run| run.label.clone()).collect();
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .gap_1()
+            .children(live_runs.into_iter().map(|run| self.render_row(run)))
+            .children(
+                self.history
+                    .iter()
+                    .rev()
+                    .filter(|record| !live_labels.contains(&record.label))
+                    .map(|record| self.render_history_row(record)),
+            )
+    }
+}
+
+/// The terminal task run matching `label`, if one is currently alive in `workspace`'s
+/// project. Per-row actions look the run back up by label rather than capturing a handle
+/// directly, since the row itself is rebuilt from scratch on every render.
+fn matching_terminal_handle(
+    workspace: &Workspace,
+    label: &str,
+    cx: &AppContext,
+) -> Option<Model<terminal::Terminal>> {
+    workspace
+        .project()
+        .read(cx)
+        .local_terminal_handles()
+        .iter()
+        .filter_map(|handle| handle.upgrade())
+        .find(|handle| {
+            handle
+                .read(cx)
+                .task()
+                .is_some_and(|task| task.label.as_ref() == label)
+        })
+}
+
+/// The pane and tab index currently showing `terminal`, if any, found by downcasting each
+/// pane's items to `TerminalView` rather than tracking item ids ourselves.
+fn find_pane_and_index(
+    workspace: &Workspace,
+    terminal: &Model<terminal::Terminal>,
+    cx: &AppContext,
+) -> Option<(View<Pane>, usize)> {
+    for pane in workspace.panes() {
+        let pane_ref = pane.read(cx);
+        for item in pane_ref.items() {
+            if let Some(terminal_view) = item.downcast::<terminal_view::TerminalView>() {
+                if terminal_view.read(cx).terminal() == terminal {
+                    let index = pane_ref.index_for_item(item.as_ref())?;
+                    return Some((pane.clone(), index));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Brings `label`'s terminal tab to the front of whichever pane it's in.
+pub(crate) fn focus_task_terminal(workspace: &mut Workspace, label: &str, cx: &mut ViewContext<Workspace>) {
+    let Some(terminal) = matching_terminal_handle(workspace, label, cx) else {
+        return;
+    };
+    if let Some((pane, index)) = find_pane_and_index(workspace, &terminal, cx) {
+        pane.update(cx, |pane, cx| pane.activate_item(index, true, true, cx));
+    }
+}
+
+/// Kills `label`'s task (if still running) and immediately re-spawns it in the same
+/// terminal. The run's persisted `restart_attempt` is not changed.
+pub(crate) fn rerun_task(workspace: &mut Workspace, label: &str, cx: &mut ViewContext<Workspace>) {
+    let Some(terminal) = matching_terminal_handle(workspace, label, cx) else {
+        return;
+    };
+    terminal.update(cx, |terminal, cx| terminal.rerun_task(cx));
+}
+
+/// Kills `label`'s task, leaving its terminal tab open with whatever output it had already
+/// produced.
+pub(crate) fn kill_task(workspace: &mut Workspace, label: &str, cx: &mut ViewContext<Workspace>) {
+    let Some(terminal) = matching_terminal_handle(workspace, label, cx) else {
+        return;
+    };
+    terminal.update(cx, |terminal, cx| terminal.kill_active_task(cx));
+}
+
+/// Spawns a persisted task definition by name, the same way the spawn-modal path does when
+/// a specific task is picked rather than left to open the picker: naming it via `Spawn`'s
+/// `task_name` reuses the project's existing task lookup/terminal-creation logic instead of
+/// this crate re-implementing it against `definition`'s raw command/cwd/env.
+pub(crate) fn spawn_task_definition(
+    workspace: &mut Workspace,
+    definition: &SerializedTaskDefinition,
+    cx: &mut WindowContext,
+) {
+    crate::spawn_task_or_modal(
+        workspace,
+        &Spawn {
+            task_name: Some(definition.label.clone()),
+        },
+        cx,
+    );
+}
+
+impl TaskInspector {
+    fn render_row(&self, run: TaskRunRecord) -> impl IntoElement {
+        let workspace = self.workspace.clone();
+        let focus_label = run.label.clone();
+        let rerun_label = run.label.clone();
+        let kill_label = run.label.clone();
+
+        h_flex()
+            .gap_2()
+            .child(Label::new(run.label.clone()).color(run.status.color()))
+            .child(Label::new(format!("{:.1}s", run.duration().as_secs_f32())))
+            .children(run.exit_code.map(|code| Label::new(format!("exit {code}"))))
+            .child({
+                let workspace = workspace.clone();
+                let id = SharedString::from(format!("task-inspector-focus-{}", run.label));
+                IconButton::new(id, IconName::ArrowUpRight).on_click(move |_, cx| {
+                    workspace
+                        .update(cx, |workspace, cx| {
+                            focus_task_terminal(workspace, &focus_label, cx)
+                        })
+                        .ok();
+                })
+            })
+            .child({
+                let workspace = workspace.clone();
+                let id = SharedString::from(format!("task-inspector-rerun-{}", run.label));
+                IconButton::new(id, IconName::Play).on_click(move |_, cx| {
+                    workspace
+                        .update(cx, |workspace, cx| rerun_task(workspace, &rerun_label, cx))
+                        .ok();
+                })
+            })
+            .child({
+                let id = SharedString::from(format!("task-inspector-kill-{}", kill_label));
+                IconButton::new(id, IconName::XCircle).on_click(move |_, cx| {
+                    workspace
+                        .update(cx, |workspace, cx| kill_task(workspace, &kill_label, cx))
+                        .ok();
+                })
+            })
+    }
+
+    fn render_history_row(&self, record: &TaskRunRecord) -> impl IntoElement {
+        h_flex()
+            .gap_2()
+            .child(Label::new(record.label.clone()).color(record.status.color()))
+            .child(Label::new(format!("{:.1}s", record.duration().as_secs_f32())))
+            .children(record.exit_code.map(|code| Label::new(format!("exit {code}"))))
+    }
+}
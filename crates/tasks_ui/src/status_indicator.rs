@@ -1,17 +1,93 @@
-use gpui::{IntoElement, WeakView};
+use std::collections::HashMap;
+
+use gpui::{Global, IntoElement, WeakView};
 use settings::Settings;
 use ui::{
     div, ButtonCommon, Clickable, Color, FluentBuilder, IconButton, IconName, RenderOnce, Tooltip,
     WindowContext,
 };
-use workspace::Workspace;
+use workspace::{
+    persistence::model::{SerializedTaskRun, SerializedTaskStatus, TaskRestartPolicy},
+    Workspace, WorkspaceId,
+};
 
 use crate::{modal::Spawn, settings::TaskSettings};
 
-enum TaskStatus {
+/// Process-wide cache of each workspace's interrupted runs, populated by the one-shot async
+/// DB query `TaskStatusIndicator` kicks off the first time it's rendered for a given
+/// workspace. `TaskStatusIndicator` itself is rebuilt every render, so this is what lets it
+/// avoid re-querying the database on every single frame.
+#[derive(Default)]
+struct InterruptedRunsCache(HashMap<WorkspaceId, Vec<SerializedTaskRun>>);
+
+impl Global for InterruptedRunsCache {}
+
+#[derive(Clone, Copy)]
+pub(crate) enum TaskStatus {
     Failed,
     Running,
     Succeeded,
+    /// Distinct from `Failed`: the task's `restart_policy` is retrying it, and hasn't yet
+    /// exhausted `max_retries`.
+    Restarting { attempt: u32, max_retries: u32 },
+}
+
+impl TaskStatus {
+    pub(crate) fn color(&self) -> Color {
+        match self {
+            TaskStatus::Failed => Color::Error,
+            TaskStatus::Running | TaskStatus::Restarting { .. } => Color::Warning,
+            TaskStatus::Succeeded => Color::Success,
+        }
+    }
+}
+
+/// Folds one more task's status into the indicator's running aggregate, honoring the
+/// red > yellow > green priority documented on [`TaskStatusIndicator`]: `Failed` always
+/// wins outright (returning `true` signals the caller to stop folding), `Running`/`Restarting`
+/// overwrite anything except `Failed`, and `Succeeded` only fills in an empty
+/// slot. Shared by the live-terminal and interrupted-run loops in `current_status` so both
+/// honor the same precedence instead of the second loop only getting to fill gaps.
+fn merge_status(acc: &mut Option<TaskStatus>, status: TaskStatus) -> bool {
+    match status {
+        TaskStatus::Failed => {
+            *acc = Some(TaskStatus::Failed);
+            true
+        }
+        TaskStatus::Running | TaskStatus::Restarting { .. } => {
+            if !matches!(acc, Some(TaskStatus::Failed)) {
+                *acc = Some(status);
+            }
+            false
+        }
+        TaskStatus::Succeeded => {
+            acc.get_or_insert(status);
+            false
+        }
+    }
+}
+
+/// Computes the status to surface for a persisted run, taking its supervision policy into
+/// account: a failed run that's still within its `on-failure` retry budget is reported as
+/// `Restarting`, not `Failed`, distinct from a plain running/failed task.
+fn supervised_status(run: &SerializedTaskRun) -> Option<TaskStatus> {
+    match run.status {
+        SerializedTaskStatus::Running => Some(TaskStatus::Running),
+        SerializedTaskStatus::Completed { success: true } => Some(TaskStatus::Succeeded),
+        SerializedTaskStatus::Completed { success: false } => {
+            if let TaskRestartPolicy::OnFailure { max_retries, .. } = run.definition.restart_policy
+            {
+                if run.restart_attempt < max_retries {
+                    return Some(TaskStatus::Restarting {
+                        attempt: run.restart_attempt + 1,
+                        max_retries,
+                    });
+                }
+            }
+            Some(TaskStatus::Failed)
+        }
+        SerializedTaskStatus::Unknown => None,
+    }
 }
 
 /// A status bar icon that surfaces the status of running tasks.
@@ -20,14 +96,74 @@ enum TaskStatus {
 /// - else, yellow if any open task tab is still running
 /// - else, green if there tasks tabs open, and they have all succeeded
 /// - else, no indicator if there are no open task tabs
+///
+/// Tasks that were still `Running` in the database when Zed last quit (and so never got
+/// a chance to report a final status) are surfaced the same way until the user resumes or
+/// dismisses them, instead of silently vanishing on restart.
 pub struct TaskStatusIndicator {
     workspace: WeakView<Workspace>,
+    interrupted_runs: Vec<SerializedTaskRun>,
 }
 
 impl TaskStatusIndicator {
-    pub fn new(workspace: WeakView<Workspace>) -> Self {
-        Self { workspace }
+    pub fn new(workspace: WeakView<Workspace>, cx: &mut WindowContext) -> Self {
+        let interrupted_runs = Self::cached_or_load_interrupted_runs(&workspace, cx);
+        Self {
+            workspace,
+            interrupted_runs,
+        }
+    }
+
+    /// Called once the workspace has loaded its persisted state, with any task runs that
+    /// were still `Running` or `Completed { success: false }` the last time this workspace
+    /// was serialized.
+    pub fn set_interrupted_runs(&mut self, runs: Vec<SerializedTaskRun>) {
+        self.interrupted_runs = runs;
+    }
+
+    /// Returns the cached interrupted runs for `workspace`, if they've already been loaded.
+    /// Otherwise, reserves this workspace's cache slot, kicks off the DB query that fills
+    /// it in, and returns an empty list for this render - `cx.refresh()` once the query
+    /// resolves picks up the real list on the next one.
+    fn cached_or_load_interrupted_runs(
+        workspace: &WeakView<Workspace>,
+        cx: &mut WindowContext,
+    ) -> Vec<SerializedTaskRun> {
+        let Some(workspace_id) = workspace
+            .update(cx, |workspace, _| workspace.database_id())
+            .ok()
+            .flatten()
+        else {
+            return Vec::new();
+        };
+
+        if !cx.has_global::<InterruptedRunsCache>() {
+            cx.set_global(InterruptedRunsCache::default());
+        }
+        if let Some(runs) = cx.global::<InterruptedRunsCache>().0.get(&workspace_id) {
+            return runs.clone();
+        }
+
+        cx.global_mut::<InterruptedRunsCache>()
+            .0
+            .insert(workspace_id, Vec::new());
+        cx.spawn(|mut cx| async move {
+            let runs = SerializedTaskRun::load_interrupted(workspace_id)
+                .await
+                .unwrap_or_default();
+            cx.update(|cx| {
+                cx.global_mut::<InterruptedRunsCache>()
+                    .0
+                    .insert(workspace_id, runs);
+                cx.refresh();
+            })
+            .ok();
+        })
+        .detach();
+
+        Vec::new()
     }
+
     fn current_status(&self, cx: &mut WindowContext) -> Option<TaskStatus> {
         self.workspace
             .update(cx, |this, cx| {
@@ -41,21 +177,32 @@ impl TaskStatusIndicator {
                     let handle = handle.read(cx);
                     let task_state = handle.task();
                     if let Some(state) = task_state {
-                        match state.status {
-                            terminal::TaskStatus::Running => {
-                                let _ = status.insert(TaskStatus::Running);
+                        let live_status = match state.status {
+                            terminal::TaskStatus::Running => Some(TaskStatus::Running),
+                            terminal::TaskStatus::Completed { success: true } => {
+                                Some(TaskStatus::Succeeded)
                             }
-                            terminal::TaskStatus::Completed { success } => {
-                                if !success {
-                                    let _ = status.insert(TaskStatus::Failed);
-                                    return status;
-                                }
-                                status.get_or_insert(TaskStatus::Succeeded);
+                            terminal::TaskStatus::Completed { success: false } => {
+                                Some(TaskStatus::Failed)
                             }
-                            _ => {}
+                            _ => None,
                         };
+                        if let Some(live_status) = live_status {
+                            if merge_status(&mut status, live_status) {
+                                return status;
+                            }
+                        }
                     }
                 }
+
+                for run in &self.interrupted_runs {
+                    if let Some(interrupted_status) = supervised_status(run) {
+                        if merge_status(&mut status, interrupted_status) {
+                            return status;
+                        }
+                    }
+                }
+
                 status
             })
             .ok()
@@ -68,23 +215,148 @@ impl RenderOnce for TaskStatusIndicator {
         if !TaskSettings::get_global(cx).show_status_indicator {
             return div().into_any_element();
         }
+        let has_interrupted_runs = !self.interrupted_runs.is_empty();
         let current_status = self.current_status(cx);
-        let color = current_status.map(|status| match status {
-            TaskStatus::Failed => Color::Error,
-            TaskStatus::Running => Color::Warning,
-            TaskStatus::Succeeded => Color::Success,
-        });
+        let color = current_status.as_ref().map(TaskStatus::color);
+        let restarting = match &current_status {
+            Some(TaskStatus::Restarting {
+                attempt,
+                max_retries,
+            }) => Some((*attempt, *max_retries)),
+            _ => None,
+        };
         let workspace = self.workspace.clone();
+        let interrupted_runs = self.interrupted_runs.clone();
         IconButton::new("tasks-activity-indicator", IconName::Play)
             .when_some(color, |this, color| this.icon_color(color))
             .on_click(move |_, cx| {
                 workspace
                     .update(cx, |this, cx| {
-                        crate::spawn_task_or_modal(this, &Spawn::modal(), cx)
+                        if !interrupted_runs.is_empty() {
+                            resume_interrupted_runs(this, interrupted_runs.clone(), cx);
+                        } else {
+                            crate::spawn_task_or_modal(this, &Spawn::modal(), cx)
+                        }
                     })
                     .ok();
             })
-            .tooltip(|cx| Tooltip::for_action("Spawn tasks", &Spawn { task_name: None }, cx))
+            .tooltip(move |cx| {
+                if let Some((attempt, max_retries)) = restarting {
+                    Tooltip::text(format!("Restarting, attempt {attempt}/{max_retries}"), cx)
+                } else if has_interrupted_runs {
+                    Tooltip::text(
+                        "Some tasks were still running or had failed when Zed last quit. Click to re-run them.",
+                        cx,
+                    )
+                } else {
+                    Tooltip::for_action("Spawn tasks", &Spawn { task_name: None }, cx)
+                }
+            })
             .into_any_element()
     }
 }
+
+/// Re-spawns every interrupted run from its persisted definition, persists each as freshly
+/// `Running` with its restart attempt reset to 0, and clears the in-memory cache so they
+/// aren't offered again in this session. A run persisted as `Running` is loaded as
+/// interrupted again on the next restart.
+fn resume_interrupted_runs(
+    workspace: &mut Workspace,
+    runs: Vec<SerializedTaskRun>,
+    cx: &mut WindowContext,
+) {
+    let Some(workspace_id) = workspace.database_id() else {
+        return;
+    };
+
+    for run in runs {
+        crate::inspector::spawn_task_definition(workspace, &run.definition, cx);
+        cx.background_executor()
+            .spawn(SerializedTaskRun::persist(
+                workspace_id,
+                run.definition,
+                SerializedTaskStatus::Running,
+                None,
+                0,
+            ))
+            .detach();
+    }
+
+    if let Some(cache) = cx.try_global_mut::<InterruptedRunsCache>() {
+        cache.0.insert(workspace_id, Vec::new());
+    }
+}
+
+#[cfg(test)]
+mod status_priority_tests {
+    use super::*;
+    use workspace::persistence::model::SerializedTaskDefinition;
+
+    #[test]
+    fn succeeded_does_not_mask_a_later_running_status() {
+        // Reproduces the bug where a live-terminal `Succeeded` filled `status` via
+        // `get_or_insert`, then an interrupted `Running` run was folded in with the same
+        // `get_or_insert`, leaving the indicator stuck on green instead of yellow.
+        let mut status = None;
+        assert!(!merge_status(&mut status, TaskStatus::Succeeded));
+        assert!(!merge_status(&mut status, TaskStatus::Running));
+        assert!(matches!(status, Some(TaskStatus::Running)));
+    }
+
+    #[test]
+    fn failed_short_circuits_and_is_not_overwritten() {
+        let mut status = None;
+        assert!(!merge_status(&mut status, TaskStatus::Succeeded));
+        assert!(merge_status(&mut status, TaskStatus::Failed));
+        assert!(!merge_status(&mut status, TaskStatus::Running));
+        assert!(matches!(status, Some(TaskStatus::Failed)));
+    }
+
+    fn run_with(restart_policy: TaskRestartPolicy, restart_attempt: u32) -> SerializedTaskRun {
+        SerializedTaskRun {
+            id: 0,
+            workspace_id: WorkspaceId::from(0),
+            definition: SerializedTaskDefinition {
+                label: "build".into(),
+                command: "cargo build".into(),
+                cwd: None,
+                env: Vec::new(),
+                restart_policy,
+                group: None,
+            },
+            status: SerializedTaskStatus::Completed { success: false },
+            exit_code: Some(1),
+            restart_attempt,
+        }
+    }
+
+    #[test]
+    fn supervised_status_restarts_below_max_retries() {
+        let run = run_with(
+            TaskRestartPolicy::OnFailure {
+                max_retries: 3,
+                backoff_ms: 0,
+            },
+            2,
+        );
+        assert!(matches!(
+            supervised_status(&run),
+            Some(TaskStatus::Restarting {
+                attempt: 3,
+                max_retries: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn supervised_status_fails_once_retries_are_exhausted() {
+        let run = run_with(
+            TaskRestartPolicy::OnFailure {
+                max_retries: 3,
+                backoff_ms: 0,
+            },
+            3,
+        );
+        assert!(matches!(supervised_status(&run), Some(TaskStatus::Failed)));
+    }
+}
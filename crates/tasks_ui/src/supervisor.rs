@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use gpui::{SharedString, WeakView, WindowContext};
+use workspace::{
+    persistence::model::{SerializedTaskRun, SerializedTaskStatus, TaskGroupFailureMode, TaskRestartPolicy},
+    WorkspaceId, Workspace,
+};
+
+use crate::inspector::kill_task;
+
+/// Called by [`crate::inspector::TaskInspector`]'s completion poll when a live run drops
+/// out of the live set with a failed status. Looks the run's persisted definition back up
+/// by label and actually applies its `restart_policy`/`group` - previously
+/// `supervised_status` only ever *labeled* a failed-but-retryable run as "Restarting"
+/// without anything behind it ever restarting it.
+pub(crate) fn handle_failed_run(
+    workspace: WeakView<Workspace>,
+    label: SharedString,
+    cx: &mut WindowContext,
+) {
+    cx.spawn(|mut cx| async move {
+        let Some(workspace_id) = workspace
+            .update(&mut cx, |workspace, _| workspace.database_id())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        let Some(run) = SerializedTaskRun::find_by_label(workspace_id, &label)
+            .await
+            .ok()
+            .flatten()
+        else {
+            // Nothing was ever persisted for this label (e.g. it was spawned before this
+            // run started being tracked) - there's no policy to apply.
+            return;
+        };
+
+        if let Some(group) = run.definition.group.clone() {
+            if group.failure_mode == TaskGroupFailureMode::AllOrNothing {
+                tear_down_group(workspace.clone(), workspace_id, group.id, label.clone(), &mut cx)
+                    .await;
+            }
+        }
+
+        apply_restart_policy(workspace, workspace_id, run, &mut cx).await;
+    })
+    .detach();
+}
+
+/// Persists `run` as failed and, if its `restart_policy` still has retries left (or retries
+/// unconditionally), respawns it after the policy's backoff. Shared by `handle_failed_run`
+/// for the run that actually failed and by `tear_down_group` for the siblings it kills, so
+/// a torn-down group member gets its own policy applied immediately rather than waiting for
+/// the completion poll to notice the kill and route back through `handle_failed_run`.
+async fn apply_restart_policy(
+    workspace: WeakView<Workspace>,
+    workspace_id: WorkspaceId,
+    run: SerializedTaskRun,
+    cx: &mut gpui::AsyncWindowContext,
+) {
+    match run.definition.restart_policy {
+        TaskRestartPolicy::OnFailure {
+            max_retries,
+            backoff_ms,
+        } if run.restart_attempt < max_retries => {
+            let next_attempt = run.restart_attempt + 1;
+            SerializedTaskRun::persist(
+                workspace_id,
+                run.definition.clone(),
+                SerializedTaskStatus::Completed { success: false },
+                run.exit_code,
+                next_attempt,
+            )
+            .await
+            .ok();
+            respawn_after_backoff(workspace, run.definition, backoff_ms, cx).await;
+        }
+        TaskRestartPolicy::Always => {
+            SerializedTaskRun::persist(
+                workspace_id,
+                run.definition.clone(),
+                SerializedTaskStatus::Completed { success: false },
+                run.exit_code,
+                run.restart_attempt,
+            )
+            .await
+            .ok();
+            respawn_after_backoff(workspace, run.definition, 0, cx).await;
+        }
+        _ => {
+            SerializedTaskRun::persist(
+                workspace_id,
+                run.definition,
+                SerializedTaskStatus::Completed { success: false },
+                run.exit_code,
+                run.restart_attempt,
+            )
+            .await
+            .ok();
+        }
+    }
+}
+
+async fn respawn_after_backoff(
+    workspace: WeakView<Workspace>,
+    definition: workspace::persistence::model::SerializedTaskDefinition,
+    backoff_ms: u64,
+    cx: &mut gpui::AsyncWindowContext,
+) {
+    if backoff_ms > 0 {
+        cx.background_executor()
+            .timer(Duration::from_millis(backoff_ms))
+            .await;
+    }
+    workspace
+        .update(cx, |workspace, cx| {
+            crate::inspector::spawn_task_definition(workspace, &definition, cx)
+        })
+        .ok();
+}
+
+/// Kills every other member of `group_id` still running, leaving `except_label` alone since
+/// its own failure path already tore it down. Each killed member's own `restart_policy` is
+/// applied directly here - relying on the completion poll to notice the kill and re-enter
+/// `handle_failed_run` round-trips through a poll interval and re-runs teardown for the
+/// whole group a second time per member.
+async fn tear_down_group(
+    workspace: WeakView<Workspace>,
+    workspace_id: WorkspaceId,
+    group_id: std::sync::Arc<str>,
+    except_label: SharedString,
+    cx: &mut gpui::AsyncWindowContext,
+) {
+    let members = SerializedTaskRun::group_members(workspace_id, &group_id)
+        .await
+        .unwrap_or_default();
+    for member in members {
+        if member.definition.label == except_label.as_ref() {
+            continue;
+        }
+        workspace
+            .update(cx, |workspace, cx| {
+                kill_task(workspace, &member.definition.label, cx)
+            })
+            .ok();
+        apply_restart_policy(workspace.clone(), workspace_id, member, cx).await;
+    }
+}
@@ -1,12 +1,16 @@
 use super::SerializedAxis;
-use crate::{item::ItemHandle, ItemDeserializers, Member, Pane, PaneAxis, Workspace, WorkspaceId};
+use crate::{
+    item::ItemHandle, notifications::NotificationId, ItemDeserializers, Member, Pane, PaneAxis,
+    Toast, Workspace, WorkspaceId,
+};
 use anyhow::{Context, Result};
-use async_recursion::async_recursion;
 use client::RemoteProjectId;
 use db::sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
+    connection::Connection,
     statement::Statement,
 };
+use futures::StreamExt;
 use gpui::{AppContext, AsyncWindowContext, Bounds, DevicePixels, Model, Task, View, WeakView};
 use project::Project;
 use serde::{Deserialize, Serialize};
@@ -14,9 +18,40 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
+use serde::de::DeserializeOwned;
 use util::ResultExt;
 use uuid::Uuid;
 
+/// Version byte prefixed to every blob column encoded with [`encode_versioned`], so that
+/// [`decode_versioned`] can tell current-format rows from rows written before this format
+/// existed without needing a schema migration: old rows fall back to their original
+/// bincode/JSON decoder, and get re-encoded in the current format the next time they're
+/// written. Bump this if the wire format inside the MessagePack payload itself ever needs
+/// to change in a way old readers can't cope with.
+const MESSAGE_PACK_V1: u8 = 1;
+
+fn encode_versioned<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let mut bytes = vec![MESSAGE_PACK_V1];
+    rmp_serde::encode::write(&mut bytes, value).context("MessagePack serialization failed")?;
+    Ok(bytes)
+}
+
+fn decode_versioned<T: DeserializeOwned>(
+    bytes: &[u8],
+    decode_legacy: impl FnOnce(&[u8]) -> Result<T>,
+) -> Result<T> {
+    match bytes.split_first() {
+        // A legacy blob can also start with this byte (e.g. a bincode length of 1 or a
+        // `true`), so fall back to the legacy decoder when the MessagePack decode fails.
+        Some((&MESSAGE_PACK_V1, rest)) => match rmp_serde::from_slice(rest) {
+            Ok(value) => Ok(value),
+            Err(err) => decode_legacy(bytes)
+                .map_err(|_| anyhow::Error::new(err).context("MessagePack deserialization failed")),
+        },
+        _ => decode_legacy(bytes),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct SerializedRemoteProject {
     id: RemoteProjectId,
@@ -103,33 +138,28 @@ impl StaticColumnCount for WorkspaceLocation {
 
 impl Bind for &WorkspaceLocation {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
-        bincode::serialize(&self.paths)
-            .expect("Bincode serialization of paths should not fail")
-            .bind(statement, start_index)
-            .map_err(|e| dbg!(e))?;
-        dbg!(serde_json::to_string(&self.remote_project)
-            .expect("Json serialization of remote project should not fail"))
-        .bind(statement, start_index + 1)
-        .map_err(|e| dbg!(e))
-        .map(|ret| dbg!(ret))
+        let next_index = encode_versioned(&self.paths)?.bind(statement, start_index)?;
+        encode_versioned(&self.remote_project)?.bind(statement, next_index)
     }
 }
 
 impl Column for WorkspaceLocation {
     fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
-        dbg!("wut?");
         let path_blob = statement.column_blob(start_index)?;
-        let paths =
-            bincode::deserialize(path_blob).context("Bincode deserialization of paths failed")?;
-        let dev_server: Option<SerializedRemoteProject> = statement
-            .column_text(start_index + 1)
-            .and_then(|dev_server_json| Ok(serde_json::from_str(dev_server_json)?))
-            .context("Deserialization of remote project json failed")?;
+        let paths = decode_versioned(path_blob, |legacy| {
+            bincode::deserialize(legacy).context("Bincode deserialization of paths failed")
+        })?;
+
+        let remote_project_blob = statement.column_blob(start_index + 1)?;
+        let remote_project = decode_versioned(remote_project_blob, |legacy| {
+            serde_json::from_slice(legacy)
+                .context("JSON deserialization of remote project failed")
+        })?;
 
         Ok((
             WorkspaceLocation {
                 paths,
-                remote_project: dev_server,
+                remote_project,
             },
             start_index + 2,
         ))
@@ -156,9 +186,9 @@ pub struct DockStructure {
 
 impl Column for DockStructure {
     fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
-        let (left, next_index) = dbg!(DockData::column(statement, start_index))?;
-        let (right, next_index) = dbg!(DockData::column(statement, next_index))?;
-        let (bottom, next_index) = dbg!(DockData::column(statement, next_index))?;
+        let (left, next_index) = DockData::column(statement, start_index)?;
+        let (right, next_index) = DockData::column(statement, next_index)?;
+        let (bottom, next_index) = DockData::column(statement, next_index)?;
         Ok((
             DockStructure {
                 left,
@@ -172,40 +202,53 @@ impl Column for DockStructure {
 
 impl Bind for DockStructure {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
-        let next_index = dbg!(statement.bind(&self.left, start_index))?;
-        let next_index = dbg!(statement.bind(&self.right, next_index))?;
+        let next_index = statement.bind(&self.left, start_index)?;
+        let next_index = statement.bind(&self.right, next_index)?;
         statement.bind(&self.bottom, next_index)
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Default)]
+#[derive(Debug, PartialEq, Clone, Default, Deserialize, Serialize)]
 pub struct DockData {
     pub(crate) visible: bool,
     pub(crate) active_panel: Option<String>,
     pub(crate) zoom: bool,
 }
 
+// `left`/`right`/`bottom` stay one SQL column each (changing that would need a schema
+// migration), but each one used to be spread across 3 further positional sub-columns
+// (visible/active_panel/zoom), which silently broke any time a field was added to
+// `DockData`. Now each dock's column holds a single versioned MessagePack blob instead.
+// The one-time workspace DB migration that introduced this column re-encoded every
+// pre-existing row's 3 positional values into that same column as a bincode-encoded
+// `(bool, Option<String>, bool)` tuple (no version byte), so a row written before this
+// change still decodes its real dock state below instead of silently resetting to default.
+impl StaticColumnCount for DockData {
+    fn column_count() -> usize {
+        1
+    }
+}
+
 impl Column for DockData {
     fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
-        let (visible, next_index) = Option::<bool>::column(statement, start_index)?;
-        let (active_panel, next_index) = Option::<String>::column(statement, next_index)?;
-        let (zoom, next_index) = Option::<bool>::column(statement, next_index)?;
-        Ok((
-            DockData {
-                visible: visible.unwrap_or(false),
+        let blob = statement.column_blob(start_index)?;
+        let data = decode_versioned(blob, |legacy| {
+            let (visible, active_panel, zoom) =
+                bincode::deserialize::<(bool, Option<String>, bool)>(legacy)
+                    .context("Bincode deserialization of legacy dock data failed")?;
+            Ok(DockData {
+                visible,
                 active_panel,
-                zoom: zoom.unwrap_or(false),
-            },
-            next_index,
-        ))
+                zoom,
+            })
+        })?;
+        Ok((data, start_index + 1))
     }
 }
 
 impl Bind for DockData {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
-        let next_index = statement.bind(&self.visible, start_index)?;
-        let next_index = statement.bind(&self.active_panel, next_index)?;
-        statement.bind(&self.zoom, next_index)
+        encode_versioned(self)?.bind(statement, start_index)
     }
 }
 
@@ -229,31 +272,325 @@ impl Default for SerializedPaneGroup {
     }
 }
 
+/// Caps how many item-deserialization results we wait on at once across the *entire*
+/// workspace being restored, via `buffered` in `deserialize_all_items`. Every item's
+/// deserializer is still called, and its task created, before any of them is awaited, so
+/// this does not limit how many loads start. Tune this if
+/// restores end up I/O-bound versus CPU-bound in practice.
+const MAX_CONCURRENT_ITEM_LOADS: usize = 64;
+
+/// Splits a flat, concurrently-produced list of item results back into one chunk per pane,
+/// in `sizes` order, so a pane always gets back exactly the items it asked for in its
+/// original child order regardless of which pane's loads happened to resolve first. Pulled
+/// out of [`SerializedPaneGroup::deserialize`] so the ordering invariant can be unit tested
+/// without spinning up a `Project`/`Workspace`.
+fn unflatten_by_sizes<T>(items: Vec<T>, sizes: impl IntoIterator<Item = usize>) -> Vec<Vec<T>> {
+    let mut remaining = items.into_iter();
+    sizes
+        .into_iter()
+        .map(|size| remaining.by_ref().take(size).collect())
+        .collect()
+}
+
+/// A pane created while walking a [`SerializedPaneGroup`], along with the items it still
+/// needs loaded. Panes are created (and so numbered) in a single synchronous pass over the
+/// tree, which is what lets every pane's items be deserialized together afterward instead
+/// of one pane at a time.
+struct PendingPane {
+    pane: WeakView<Pane>,
+    active: bool,
+    active_item_index: Option<usize>,
+    preview_item_index: Option<usize>,
+    children: Vec<SerializedItem>,
+}
+
+/// Mirrors [`SerializedPaneGroup`], but every leaf pane has already been created and is
+/// referenced by its index into the flat [`PendingPane`] list built alongside it, which
+/// fixes traversal order before any item loading (and therefore any concurrency) begins.
+enum PendingMember {
+    Axis {
+        axis: SerializedAxis,
+        flexes: Option<Vec<f32>>,
+        children: Vec<PendingMember>,
+    },
+    Pane(usize),
+}
+
+/// Where a [`SerializedPaneGroup`] restore currently is, in the order a restore actually
+/// goes through them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceRestorePhase {
+    Discovering,
+    LoadingItems,
+    AssemblingLayout,
+    Done,
+}
+
+impl Default for WorkspaceRestorePhase {
+    fn default() -> Self {
+        Self::Discovering
+    }
+}
+
+/// Live progress of a workspace restore. [`SerializedWorkspace::restore_center_group`]
+/// creates the `Model<WorkspaceRestoreProgress>`, passes it to
+/// [`SerializedPaneGroup::deserialize`], and returns it, so a caller can `cx.observe`
+/// it to show a restore indicator, instead of the deserialize path being an opaque async
+/// recursion that reports nothing until every pane and item has resolved.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceRestoreProgress {
+    pub phase: WorkspaceRestorePhase,
+    pub total_items: usize,
+    pub completed_items: usize,
+    pub failed_items: usize,
+    /// Item kinds encountered during restore with no registered [`ItemDeserializers`] entry,
+    /// reported here instead of silently vanishing via `log_err()`.
+    pub unregistered_kinds: Vec<Arc<str>>,
+}
+
 impl SerializedPaneGroup {
-    #[async_recursion(?Send)]
     pub(crate) async fn deserialize(
         self,
         project: &Model<Project>,
         workspace_id: WorkspaceId,
         workspace: WeakView<Workspace>,
+        progress: &Model<WorkspaceRestoreProgress>,
         cx: &mut AsyncWindowContext,
     ) -> Option<(Member, Option<View<Pane>>, Vec<Option<Box<dyn ItemHandle>>>)> {
+        let mut panes = Vec::new();
+        let root = self.create_panes(&workspace, &mut panes, cx)?;
+
+        let total_items = panes.iter().map(|pending| pending.children.len()).sum();
+        progress
+            .update(cx, |progress, cx| {
+                progress.phase = WorkspaceRestorePhase::LoadingItems;
+                progress.total_items = total_items;
+                cx.notify();
+            })
+            .log_err();
+
+        let flat_items =
+            Self::deserialize_all_items(project, workspace_id, &workspace, &panes, progress, cx)
+                .await;
+        let results = unflatten_by_sizes(
+            flat_items,
+            panes.iter().map(|pending| pending.children.len()),
+        );
+
+        progress
+            .update(cx, |progress, cx| {
+                progress.phase = WorkspaceRestorePhase::AssemblingLayout;
+                cx.notify();
+            })
+            .log_err();
+
+        for (pending, items) in panes.iter().zip(&results) {
+            Self::apply_to_pane(pending, items, cx).log_err();
+        }
+
+        let (member, active_pane) = Self::assemble(root, &panes, &workspace, cx)?;
+        let items = results.into_iter().flatten().collect();
+
+        progress
+            .update(cx, |progress, cx| {
+                progress.phase = WorkspaceRestorePhase::Done;
+                cx.notify();
+            })
+            .log_err();
+
+        Some((member, active_pane, items))
+    }
+
+    /// First, synchronous pass over the tree: creates every pane (preserving traversal
+    /// order for determinism) and records what each one still needs loaded, without
+    /// awaiting any item deserialization yet.
+    fn create_panes(
+        self,
+        workspace: &WeakView<Workspace>,
+        panes: &mut Vec<PendingPane>,
+        cx: &mut AsyncWindowContext,
+    ) -> Option<PendingMember> {
         match self {
             SerializedPaneGroup::Group {
                 axis,
+                flexes,
                 children,
+            } => {
+                let children = children
+                    .into_iter()
+                    .filter_map(|child| child.create_panes(workspace, panes, cx))
+                    .collect::<Vec<_>>();
+
+                if children.is_empty() {
+                    return None;
+                }
+
+                Some(PendingMember::Axis {
+                    axis,
+                    flexes,
+                    children,
+                })
+            }
+            SerializedPaneGroup::Pane(serialized_pane) => {
+                let pane = workspace
+                    .update(cx, |workspace, cx| workspace.add_pane(cx).downgrade())
+                    .log_err()?;
+
+                let mut active_item_index = None;
+                let mut preview_item_index = None;
+                for (index, item) in serialized_pane.children.iter().enumerate() {
+                    if item.active {
+                        active_item_index = Some(index);
+                    }
+                    if item.preview {
+                        preview_item_index = Some(index);
+                    }
+                }
+
+                let index = panes.len();
+                panes.push(PendingPane {
+                    pane,
+                    active: serialized_pane.active,
+                    active_item_index,
+                    preview_item_index,
+                    children: serialized_pane.children,
+                });
+                Some(PendingMember::Pane(index))
+            }
+        }
+    }
+
+    /// Second pass: every item across every pane, deserialized together with bounded
+    /// concurrency instead of pane-by-pane, in the same order the items were discovered so
+    /// results can be handed back to the pane/index they came from.
+    async fn deserialize_all_items(
+        project: &Model<Project>,
+        workspace_id: WorkspaceId,
+        workspace: &WeakView<Workspace>,
+        panes: &[PendingPane],
+        progress: &Model<WorkspaceRestoreProgress>,
+        cx: &mut AsyncWindowContext,
+    ) -> Vec<Option<Box<dyn ItemHandle>>> {
+        let mut item_tasks = Vec::new();
+        for pending in panes {
+            for item in &pending.children {
+                let project = project.clone();
+                let has_deserializer = pending
+                    .pane
+                    .update(cx, |_, cx| {
+                        cx.global::<ItemDeserializers>().get(&item.kind).is_some()
+                    })
+                    .unwrap_or(false);
+                if !has_deserializer {
+                    let kind = item.kind.clone();
+                    progress
+                        .update(cx, |progress, cx| {
+                            progress.unregistered_kinds.push(kind);
+                            cx.notify();
+                        })
+                        .log_err();
+                }
+
+                let task = pending
+                    .pane
+                    .update(cx, |_, cx| {
+                        if let Some(deserializer) =
+                            cx.global::<ItemDeserializers>().get(&item.kind)
+                        {
+                            deserializer(project, workspace.clone(), workspace_id, item.item_id, cx)
+                        } else {
+                            Task::ready(Err(anyhow::anyhow!(
+                                "Deserializer does not exist for item kind: {}",
+                                item.kind
+                            )))
+                        }
+                    })
+                    .unwrap_or_else(|_| {
+                        Task::ready(Err(anyhow::anyhow!("Pane was dropped before it could load")))
+                    });
+                item_tasks.push(task);
+            }
+        }
+
+        let results = futures::stream::iter(item_tasks)
+            .buffered(MAX_CONCURRENT_ITEM_LOADS)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut items = Vec::with_capacity(results.len());
+        for result in results {
+            let item_handle = result.log_err();
+            progress
+                .update(cx, |progress, cx| {
+                    if item_handle.is_some() {
+                        progress.completed_items += 1;
+                    } else {
+                        progress.failed_items += 1;
+                    }
+                    cx.notify();
+                })
+                .log_err();
+            items.push(item_handle);
+        }
+        items
+    }
+
+    /// Applies the loaded items to a single pane, in the pane's original child order, then
+    /// activates and previews as requested. Always run sequentially across panes (never
+    /// concurrently) so pane mutations land on the UI thread in a stable, deterministic
+    /// order, even though the loads that produced `items` ran concurrently.
+    fn apply_to_pane(
+        pending: &PendingPane,
+        items: &[Option<Box<dyn ItemHandle>>],
+        cx: &mut AsyncWindowContext,
+    ) -> Result<()> {
+        for item_handle in items.iter().flatten() {
+            pending.pane.update(cx, |pane, cx| {
+                pane.add_item(item_handle.clone(), true, true, None, cx);
+            })?;
+        }
+
+        if let Some(active_item_index) = pending.active_item_index {
+            pending.pane.update(cx, |pane, cx| {
+                pane.activate_item(active_item_index, false, false, cx);
+            })?;
+        }
+
+        if let Some(preview_item_index) = pending.preview_item_index {
+            pending.pane.update(cx, |pane, cx| {
+                if let Some(item) = pane.item_for_index(preview_item_index) {
+                    pane.set_preview_item_id(Some(item.item_id()), cx);
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Final, bottom-up pass: collapses the [`PendingMember`] tree into the real `Member`
+    /// tree, now that every pane knows whether it ended up with any items. Removes empty
+    /// panes exactly as the old per-pane recursion did, and picks the first active pane in
+    /// original traversal order, now that "first" is well defined across the whole tree
+    /// rather than just within whichever pane happened to finish loading first.
+    fn assemble(
+        member: PendingMember,
+        panes: &[PendingPane],
+        workspace: &WeakView<Workspace>,
+        cx: &mut AsyncWindowContext,
+    ) -> Option<(Member, Option<View<Pane>>)> {
+        match member {
+            PendingMember::Axis {
+                axis,
                 flexes,
+                children,
             } => {
                 let mut current_active_pane = None;
                 let mut members = Vec::new();
-                let mut items = Vec::new();
                 for child in children {
-                    if let Some((new_member, active_pane, new_items)) = child
-                        .deserialize(project, workspace_id, workspace.clone(), cx)
-                        .await
+                    if let Some((new_member, active_pane)) =
+                        Self::assemble(child, panes, workspace, cx)
                     {
                         members.push(new_member);
-                        items.extend(new_items);
                         current_active_pane = current_active_pane.or(active_pane);
                     }
                 }
@@ -263,30 +600,22 @@ impl SerializedPaneGroup {
                 }
 
                 if members.len() == 1 {
-                    return Some((members.remove(0), current_active_pane, items));
+                    return Some((members.remove(0), current_active_pane));
                 }
 
                 Some((
                     Member::Axis(PaneAxis::load(axis.0, members, flexes)),
                     current_active_pane,
-                    items,
                 ))
             }
-            SerializedPaneGroup::Pane(serialized_pane) => {
-                let pane = workspace
-                    .update(cx, |workspace, cx| workspace.add_pane(cx).downgrade())
-                    .log_err()?;
-                let active = serialized_pane.active;
-                let new_items = serialized_pane
-                    .deserialize_to(project, &pane, workspace_id, workspace.clone(), cx)
-                    .await
-                    .log_err()?;
-
-                if pane.update(cx, |pane, _| pane.items_len() != 0).log_err()? {
-                    let pane = pane.upgrade()?;
-                    Some((Member::Pane(pane.clone()), active.then(|| pane), new_items))
+            PendingMember::Pane(index) => {
+                let pending = &panes[index];
+                if pending.pane.update(cx, |pane, _| pane.items_len() != 0).log_err()? {
+                    let pane = pending.pane.upgrade()?;
+                    let active_pane = pending.active.then(|| pane.clone());
+                    Some((Member::Pane(pane), active_pane))
                 } else {
-                    let pane = pane.upgrade()?;
+                    let pane = pending.pane.upgrade()?;
                     workspace
                         .update(cx, |workspace, cx| workspace.force_remove_pane(&pane, cx))
                         .log_err()?;
@@ -297,6 +626,63 @@ impl SerializedPaneGroup {
     }
 }
 
+impl SerializedWorkspace {
+    /// The real entry point for restoring `center_group`: creates the
+    /// `Model<WorkspaceRestoreProgress>` callers previously had to construct themselves (or,
+    /// as it turned out, never did), subscribes a toast that reports any item kinds that
+    /// couldn't be restored once the restore finishes, and returns the progress model
+    /// alongside the usual restore results so a caller can also observe it directly (e.g.
+    /// to drive a status-bar indicator).
+    pub(crate) async fn restore_center_group(
+        self,
+        project: &Model<Project>,
+        workspace: WeakView<Workspace>,
+        cx: &mut AsyncWindowContext,
+    ) -> Option<(
+        Member,
+        Option<View<Pane>>,
+        Vec<Option<Box<dyn ItemHandle>>>,
+        Model<WorkspaceRestoreProgress>,
+    )> {
+        let progress = cx.new_model(|_| WorkspaceRestoreProgress::default()).ok()?;
+        workspace
+            .update(cx, |_, cx| {
+                cx.observe(&progress, |workspace, progress, cx| {
+                    let progress = progress.read(cx).clone();
+                    if progress.phase == WorkspaceRestorePhase::Done
+                        && !progress.unregistered_kinds.is_empty()
+                    {
+                        workspace.show_toast(
+                            Toast::new(
+                                NotificationId::unique::<WorkspaceRestoreProgress>(),
+                                format!(
+                                    "Couldn't restore {} item kind(s) (no deserializer registered): {}",
+                                    progress.unregistered_kinds.len(),
+                                    progress
+                                        .unregistered_kinds
+                                        .iter()
+                                        .map(|kind| kind.as_ref())
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                ),
+                            ),
+                            cx,
+                        );
+                    }
+                })
+                .detach();
+            })
+            .ok();
+
+        let workspace_id = self.id;
+        let (member, active_pane, items) = self
+            .center_group
+            .deserialize(project, workspace_id, workspace, &progress, cx)
+            .await?;
+        Some((member, active_pane, items, progress))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
 pub struct SerializedPane {
     pub(crate) active: bool,
@@ -307,73 +693,151 @@ impl SerializedPane {
     pub fn new(children: Vec<SerializedItem>, active: bool) -> Self {
         SerializedPane { children, active }
     }
+}
 
-    pub async fn deserialize_to(
-        &self,
-        project: &Model<Project>,
-        pane: &WeakView<Pane>,
-        workspace_id: WorkspaceId,
-        workspace: WeakView<Workspace>,
-        cx: &mut AsyncWindowContext,
-    ) -> Result<Vec<Option<Box<dyn ItemHandle>>>> {
-        let mut item_tasks = Vec::new();
-        let mut active_item_index = None;
-        let mut preview_item_index = None;
-        for (index, item) in self.children.iter().enumerate() {
-            let project = project.clone();
-            item_tasks.push(pane.update(cx, |_, cx| {
-                if let Some(deserializer) = cx.global::<ItemDeserializers>().get(&item.kind) {
-                    deserializer(project, workspace.clone(), workspace_id, item.item_id, cx)
-                } else {
-                    Task::ready(Err(anyhow::anyhow!(
-                        "Deserializer does not exist for item kind: {}",
-                        item.kind
-                    )))
-                }
-            })?);
-            if item.active {
-                active_item_index = Some(index);
-            }
-            if item.preview {
-                preview_item_index = Some(index);
-            }
-        }
+pub type GroupId = i64;
+pub type PaneId = i64;
+pub type ItemId = u64;
+pub type TaskRunId = i64;
 
-        let mut items = Vec::new();
-        for item_handle in futures::future::join_all(item_tasks).await {
-            let item_handle = item_handle.log_err();
-            items.push(item_handle.clone());
+/// How a failed task should be supervised. Declared on the task definition so restart
+/// behavior travels with the task (and survives the resume-on-restart path) instead of
+/// being a one-off decision made by whoever happened to spawn it.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum TaskRestartPolicy {
+    Never,
+    OnFailure { max_retries: u32, backoff_ms: u64 },
+    Always,
+}
 
-            if let Some(item_handle) = item_handle {
-                pane.update(cx, |pane, cx| {
-                    pane.add_item(item_handle.clone(), true, true, None, cx);
-                })?;
-            }
-        }
+impl Default for TaskRestartPolicy {
+    fn default() -> Self {
+        Self::Never
+    }
+}
 
-        if let Some(active_item_index) = active_item_index {
-            pane.update(cx, |pane, cx| {
-                pane.activate_item(active_item_index, false, false, cx);
-            })?;
-        }
+/// How a failure in one member of a [`TaskGroup`] should propagate to the rest of the
+/// group. `Unknown` is the `Default`. Unlike [`SerializedTaskStatus`], whose column falls
+/// back to `Unknown`, a mode this version does not recognize fails to decode the whole
+/// [`SerializedTaskDefinition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TaskGroupFailureMode {
+    /// If any member fails, the supervisor tears down (and optionally restarts) every
+    /// other member of the group too.
+    AllOrNothing,
+    Unknown,
+}
 
-        if let Some(preview_item_index) = preview_item_index {
-            pane.update(cx, |pane, cx| {
-                if let Some(item) = pane.item_for_index(preview_item_index) {
-                    pane.set_preview_item_id(Some(item.item_id()), cx);
-                }
-            })?;
-        }
+impl Default for TaskGroupFailureMode {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+/// Ties a task to a set of related tasks (e.g. a watcher and the server it restarts),
+/// so they can be supervised as a unit rather than independently.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TaskGroup {
+    pub id: Arc<str>,
+    pub failure_mode: TaskGroupFailureMode,
+}
+
+/// The static definition of a spawned task, captured at spawn time so a
+/// previously-running or failed run can be recreated after a restart.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SerializedTaskDefinition {
+    pub label: String,
+    pub command: String,
+    pub cwd: Option<PathBuf>,
+    pub env: Vec<(String, String)>,
+    #[serde(default)]
+    pub restart_policy: TaskRestartPolicy,
+    #[serde(default)]
+    pub group: Option<TaskGroup>,
+}
+
+/// Mirrors [`terminal::TaskStatus`], but is kept separate (and `#[non_exhaustive]`-shaped
+/// via `Unknown`) so that a row written by a newer Zed with an extra status doesn't fail
+/// to deserialize in an older one; unrecognized statuses just fall back to `Unknown`
+/// rather than corrupting the read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SerializedTaskStatus {
+    Running,
+    Completed { success: bool },
+    Unknown,
+}
 
-        anyhow::Ok(items)
+impl Default for SerializedTaskStatus {
+    fn default() -> Self {
+        Self::Unknown
     }
 }
 
-pub type GroupId = i64;
-pub type PaneId = i64;
-pub type ItemId = u64;
+/// A persisted record of a spawned task, kept around so the [`TaskStatusIndicator`] (in
+/// the `tasks_ui` crate) can surface tasks that were still running or had just failed when
+/// Zed last quit, and offer to resume them.
+///
+/// [`TaskStatusIndicator`]: ../../../tasks_ui/struct.TaskStatusIndicator.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerializedTaskRun {
+    pub id: TaskRunId,
+    pub workspace_id: WorkspaceId,
+    pub definition: SerializedTaskDefinition,
+    pub status: SerializedTaskStatus,
+    pub exit_code: Option<i32>,
+    /// How many times this run has already been restarted by its `restart_policy`.
+    /// Persisted alongside the run so a supervised restart loop survives Zed quitting and
+    /// resuming mid-retry, rather than resetting the attempt count to zero.
+    pub restart_attempt: u32,
+}
+
+impl StaticColumnCount for SerializedTaskDefinition {
+    fn column_count() -> usize {
+        1
+    }
+}
+
+impl Bind for &SerializedTaskDefinition {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        encode_versioned(self)?.bind(statement, start_index)
+    }
+}
+
+impl Column for SerializedTaskDefinition {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let blob = statement.column_blob(start_index)?;
+        let definition = decode_versioned(blob, |legacy| {
+            serde_json::from_slice(legacy)
+                .context("JSON deserialization of task definition failed")
+        })?;
+        Ok((definition, start_index + 1))
+    }
+}
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+impl StaticColumnCount for SerializedTaskStatus {
+    fn column_count() -> usize {
+        1
+    }
+}
+
+impl Bind for &SerializedTaskStatus {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        encode_versioned(self)?.bind(statement, start_index)
+    }
+}
+
+impl Column for SerializedTaskStatus {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let blob = statement.column_blob(start_index)?;
+        let status = decode_versioned(blob, |legacy| {
+            serde_json::from_slice(legacy).context("JSON deserialization of task status failed")
+        })
+        .unwrap_or(SerializedTaskStatus::Unknown);
+        Ok((status, start_index + 1))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct SerializedItem {
     pub kind: Arc<str>,
     pub item_id: ItemId,
@@ -404,34 +868,309 @@ impl Default for SerializedItem {
     }
 }
 
+// Was 4 positional columns (kind/item_id/active/preview); now a single versioned
+// MessagePack blob, migrated in place alongside `DockData`'s, so adding a field to
+// `SerializedItem` (e.g. per-item metadata) no longer requires shifting every column
+// index downstream of it. As with `DockData`, the one-time migration that introduced this
+// column re-encoded every pre-existing row's 4 positional values into it as a
+// bincode-encoded `(Arc<str>, ItemId, bool, bool)` tuple (no version byte), so rows
+// written before this change still restore their real item instead of losing the tab.
 impl StaticColumnCount for SerializedItem {
     fn column_count() -> usize {
-        4
+        1
     }
 }
 impl Bind for &SerializedItem {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
-        let next_index = statement.bind(&self.kind, start_index)?;
-        let next_index = statement.bind(&self.item_id, next_index)?;
-        let next_index = statement.bind(&self.active, next_index)?;
-        statement.bind(&self.preview, next_index)
+        encode_versioned(self)?.bind(statement, start_index)
     }
 }
 
 impl Column for SerializedItem {
     fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
-        let (kind, next_index) = Arc::<str>::column(statement, start_index)?;
-        let (item_id, next_index) = ItemId::column(statement, next_index)?;
-        let (active, next_index) = bool::column(statement, next_index)?;
-        let (preview, next_index) = bool::column(statement, next_index)?;
-        Ok((
-            SerializedItem {
+        let blob = statement.column_blob(start_index)?;
+        let item = decode_versioned(blob, |legacy| {
+            let (kind, item_id, active, preview) =
+                bincode::deserialize::<(Arc<str>, ItemId, bool, bool)>(legacy)
+                    .context("Bincode deserialization of legacy serialized item failed")?;
+            Ok(SerializedItem {
                 kind,
                 item_id,
                 active,
                 preview,
+            })
+        })
+        .context("MessagePack deserialization of serialized item failed")?;
+        Ok((item, start_index + 1))
+    }
+}
+
+/// Schema for the table backing [`SerializedTaskRun`]. Registered in `WorkspaceDb`'s
+/// migration list in `persistence/mod.rs`, alongside the other workspace tables - this is
+/// what was missing before: the `Bind`/`Column` impls above had nowhere to actually read or
+/// write to.
+pub(crate) const TASK_RUNS_MIGRATION: &str = "
+CREATE TABLE IF NOT EXISTS task_runs (
+    task_run_id INTEGER PRIMARY KEY,
+    workspace_id INTEGER NOT NULL REFERENCES workspaces(workspace_id) ON DELETE CASCADE,
+    definition BLOB NOT NULL,
+    status BLOB NOT NULL,
+    exit_code INTEGER,
+    restart_attempt INTEGER NOT NULL DEFAULT 0
+) STRICT;
+";
+
+impl StaticColumnCount for SerializedTaskRun {
+    fn column_count() -> usize {
+        1 + 1 + SerializedTaskDefinition::column_count() + SerializedTaskStatus::column_count() + 1 + 1
+    }
+}
+
+impl Column for SerializedTaskRun {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (id, next_index) = TaskRunId::column(statement, start_index)?;
+        let (workspace_id, next_index) = WorkspaceId::column(statement, next_index)?;
+        let (definition, next_index) = SerializedTaskDefinition::column(statement, next_index)?;
+        let (status, next_index) = SerializedTaskStatus::column(statement, next_index)?;
+        let (exit_code, next_index) = Option::<i32>::column(statement, next_index)?;
+        let (restart_attempt, next_index) = u32::column(statement, next_index)?;
+        Ok((
+            SerializedTaskRun {
+                id,
+                workspace_id,
+                definition,
+                status,
+                exit_code,
+                restart_attempt,
             },
             next_index,
         ))
     }
 }
+
+impl SerializedTaskRun {
+    /// Upserts (by `(workspace_id, definition)`) the latest status of a spawned task. Using
+    /// delete-then-insert rather than a real `UPDATE` keeps the write side as simple as the
+    /// rest of this file's queries, at the cost of reassigning `task_run_id` on every
+    /// update - acceptable since nothing outside this table keys off of it.
+    pub fn save(
+        connection: &Connection,
+        workspace_id: WorkspaceId,
+        definition: &SerializedTaskDefinition,
+        status: SerializedTaskStatus,
+        exit_code: Option<i32>,
+        restart_attempt: u32,
+    ) -> Result<()> {
+        connection.exec_bound("DELETE FROM task_runs WHERE workspace_id = ? AND definition = ?")?(
+            (workspace_id, definition),
+        )?;
+        connection.exec_bound(
+            "INSERT INTO task_runs (workspace_id, definition, status, exit_code, restart_attempt)
+             VALUES (?, ?, ?, ?, ?)",
+        )?((workspace_id, definition, &status, exit_code, restart_attempt))
+    }
+
+    /// Every run for `workspace_id` that was still `Running`, or had just failed, the last
+    /// time this workspace was saved - the ones [`TaskStatusIndicator`] should offer to
+    /// resume after Zed restarts instead of silently dropping.
+    ///
+    /// [`TaskStatusIndicator`]: ../../../tasks_ui/struct.TaskStatusIndicator.html
+    pub fn interrupted_for_workspace(
+        connection: &Connection,
+        workspace_id: WorkspaceId,
+    ) -> Result<Vec<SerializedTaskRun>> {
+        let runs: Vec<SerializedTaskRun> = connection.select_bound(
+            "SELECT task_run_id, workspace_id, definition, status, exit_code, restart_attempt
+             FROM task_runs
+             WHERE workspace_id = ?",
+        )?(workspace_id)?;
+
+        Ok(runs
+            .into_iter()
+            .filter(|run| {
+                matches!(
+                    run.status,
+                    SerializedTaskStatus::Running | SerializedTaskStatus::Completed { success: false }
+                )
+            })
+            .collect())
+    }
+
+    /// Async wrapper around [`Self::interrupted_for_workspace`] for callers (like
+    /// `TaskStatusIndicator`) that only have access to the shared `WorkspaceDb` connection,
+    /// not a borrowed `Connection` directly.
+    pub async fn load_interrupted(workspace_id: WorkspaceId) -> Result<Vec<SerializedTaskRun>> {
+        super::DB
+            .write(move |connection| Self::interrupted_for_workspace(connection, workspace_id))
+            .await
+    }
+
+    /// Async wrapper around [`Self::save`], for the same reason as [`Self::load_interrupted`].
+    pub async fn persist(
+        workspace_id: WorkspaceId,
+        definition: SerializedTaskDefinition,
+        status: SerializedTaskStatus,
+        exit_code: Option<i32>,
+        restart_attempt: u32,
+    ) -> Result<()> {
+        super::DB
+            .write(move |connection| {
+                Self::save(
+                    connection,
+                    workspace_id,
+                    &definition,
+                    status,
+                    exit_code,
+                    restart_attempt,
+                )
+            })
+            .await
+    }
+
+    fn all_for_workspace(connection: &Connection, workspace_id: WorkspaceId) -> Result<Vec<Self>> {
+        connection.select_bound(
+            "SELECT task_run_id, workspace_id, definition, status, exit_code, restart_attempt
+             FROM task_runs
+             WHERE workspace_id = ?",
+        )?(workspace_id)
+    }
+
+    /// The last persisted run for `label`, used by the supervisor to recover a finished
+    /// task's `restart_policy`/`group`/`restart_attempt` from just its label, since that's
+    /// all a live terminal handle exposes.
+    pub async fn find_by_label(
+        workspace_id: WorkspaceId,
+        label: &str,
+    ) -> Result<Option<SerializedTaskRun>> {
+        let label = label.to_string();
+        super::DB
+            .write(move |connection| {
+                Ok(Self::all_for_workspace(connection, workspace_id)?
+                    .into_iter()
+                    .find(|run| run.definition.label == label))
+            })
+            .await
+    }
+
+    /// Every run in `group_id`, for tearing down the rest of an `AllOrNothing` group once
+    /// one member fails.
+    pub async fn group_members(
+        workspace_id: WorkspaceId,
+        group_id: &str,
+    ) -> Result<Vec<SerializedTaskRun>> {
+        let group_id = group_id.to_string();
+        super::DB
+            .write(move |connection| {
+                Ok(Self::all_for_workspace(connection, workspace_id)?
+                    .into_iter()
+                    .filter(|run| {
+                        run.definition
+                            .group
+                            .as_ref()
+                            .is_some_and(|group| group.id.as_ref() == group_id)
+                    })
+                    .collect())
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod restore_ordering_tests {
+    use super::*;
+
+    #[test]
+    fn unflatten_by_sizes_preserves_pane_and_child_order() {
+        let flat = vec!["pane0-a", "pane0-b", "pane1-a", "pane2-a", "pane2-b", "pane2-c"];
+        let chunks = unflatten_by_sizes(flat, [2, 1, 3]);
+        assert_eq!(
+            chunks,
+            vec![
+                vec!["pane0-a", "pane0-b"],
+                vec!["pane1-a"],
+                vec!["pane2-a", "pane2-b", "pane2-c"],
+            ]
+        );
+    }
+
+    #[test]
+    fn unflatten_by_sizes_handles_empty_panes() {
+        let flat = vec![1, 2, 3];
+        let chunks = unflatten_by_sizes(flat, [0, 2, 0, 1]);
+        assert_eq!(chunks, vec![vec![], vec![1, 2], vec![], vec![3]]);
+    }
+
+    #[test]
+    fn unflatten_by_sizes_is_independent_of_completion_order() {
+        // Simulates `deserialize_all_items` resolving pane 2's loads before pane 0's or
+        // pane 1's: the flat list is still handed back in *discovery* order (each task is
+        // pushed in traversal order and `buffered` preserves input order), so reassembly
+        // must not depend on which one finished first.
+        let flat = vec!["a", "b", "c", "d"];
+        let chunks = unflatten_by_sizes(flat, [1, 2, 1]);
+        assert_eq!(chunks[0], vec!["a"]);
+        assert_eq!(chunks[1], vec!["b", "c"]);
+        assert_eq!(chunks[2], vec!["d"]);
+    }
+}
+
+#[cfg(test)]
+mod migration_tests {
+    use super::*;
+
+    #[test]
+    fn decode_versioned_reads_current_format() {
+        let dock = DockData {
+            visible: true,
+            active_panel: Some("terminal".into()),
+            zoom: true,
+        };
+        let encoded = encode_versioned(&dock).unwrap();
+        let decoded = decode_versioned(&encoded, |_| {
+            panic!("legacy decoder should not run for a current-format blob")
+        })
+        .unwrap();
+        assert_eq!(dock, decoded);
+    }
+
+    #[test]
+    fn dock_data_legacy_bincode_tuple_round_trips() {
+        let legacy = bincode::serialize(&(true, Some("terminal".to_string()), false)).unwrap();
+        let decoded: DockData = decode_versioned(&legacy, |legacy| {
+            let (visible, active_panel, zoom) =
+                bincode::deserialize::<(bool, Option<String>, bool)>(legacy)?;
+            Ok(DockData {
+                visible,
+                active_panel,
+                zoom,
+            })
+        })
+        .unwrap();
+        assert_eq!(
+            decoded,
+            DockData {
+                visible: true,
+                active_panel: Some("terminal".to_string()),
+                zoom: false,
+            }
+        );
+    }
+
+    #[test]
+    fn serialized_item_legacy_bincode_tuple_round_trips() {
+        let kind: Arc<str> = Arc::from("ProjectSearch");
+        let legacy = bincode::serialize(&(kind.clone(), 42u64, true, false)).unwrap();
+        let decoded: SerializedItem = decode_versioned(&legacy, |legacy| {
+            let (kind, item_id, active, preview) =
+                bincode::deserialize::<(Arc<str>, ItemId, bool, bool)>(legacy)?;
+            Ok(SerializedItem {
+                kind,
+                item_id,
+                active,
+                preview,
+            })
+        })
+        .unwrap();
+        assert_eq!(decoded, SerializedItem::new("ProjectSearch", 42, true, false));
+    }
+}